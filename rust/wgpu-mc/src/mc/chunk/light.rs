@@ -0,0 +1,439 @@
+use std::collections::VecDeque;
+
+use crate::mc::block::BlockState;
+use crate::mc::chunk::{
+    Chunk, ChunkPos, LoadedChunks, RenderType, CHUNK_AREA, CHUNK_HEIGHT, CHUNK_SECTION_HEIGHT,
+    CHUNK_VOLUME, CHUNK_WIDTH,
+};
+use crate::mc::BlockManager;
+
+/// Minecraft-style light levels run 0 (dark) to 15 (fully lit).
+pub const MAX_LIGHT: u8 = 15;
+
+/// Per-block light levels for one chunk: block light (from torches, lava, etc.)
+/// and sky light (from open sky), each in `0..=MAX_LIGHT`.
+#[derive(Clone, Debug)]
+pub struct LightData {
+    pub block_light: Box<[u8; CHUNK_VOLUME]>,
+    pub sky_light: Box<[u8; CHUNK_VOLUME]>,
+}
+
+impl LightData {
+    #[must_use]
+    pub fn dark() -> Self {
+        Self {
+            block_light: Box::new([0; CHUNK_VOLUME]),
+            sky_light: Box::new([0; CHUNK_VOLUME]),
+        }
+    }
+
+    #[must_use]
+    pub fn block(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.block_light[index(x, y, z)]
+    }
+
+    #[must_use]
+    pub fn sky(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.sky_light[index(x, y, z)]
+    }
+}
+
+impl Default for LightData {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+#[must_use]
+fn index(x: usize, y: usize, z: usize) -> usize {
+    (y * CHUNK_WIDTH * CHUNK_WIDTH) + (z * CHUNK_WIDTH) + x
+}
+
+/// Looks up the block at a chunk-local `(x, y, z)`, splitting `y` into its
+/// section and in-section offset the same way [`Chunk::blockstate_at_pos`]
+/// does, since `chunk.sections` is indexed by section now that sections are
+/// [`CHUNK_SECTION_HEIGHT`] blocks tall rather than one row each.
+#[must_use]
+fn state_at(chunk: &Chunk, x: usize, y: usize, z: usize) -> BlockState {
+    let section = y / CHUNK_SECTION_HEIGHT;
+    let local_y = y % CHUNK_SECTION_HEIGHT;
+    chunk.sections[section].blocks[(local_y * CHUNK_AREA) + (z * CHUNK_WIDTH) + x]
+}
+
+#[derive(Copy, Clone)]
+struct LocalPos {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+const NEIGHBORS: [(i32, i32, i32); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+/// Derives `light_opacity`/`light_emission` from [`RenderType`], the only
+/// per-block signal `BlockManager` exposes in this tree. This isn't a real
+/// per-block opacity/emission table (the real `BlockManager` doesn't exist
+/// here to extend with one), so it can't tell a torch from a flower or glass
+/// from ice; it's a coarse approximation good enough to stop light at solid
+/// terrain and let it through glass one level dimmer, which is what
+/// `compute_chunk_light` actually exercises. Replace this with a real
+/// per-block lookup once `BlockManager` carries one upstream.
+impl BlockManager {
+    #[must_use]
+    pub fn light_opacity(&self, state: BlockState) -> u8 {
+        match self.render_type(state) {
+            RenderType::Solid => MAX_LIGHT,
+            RenderType::Translucent | RenderType::Cutout => 1,
+            RenderType::Cross => 0,
+        }
+    }
+
+    #[must_use]
+    pub fn light_emission(&self, _state: BlockState) -> u8 {
+        // No block in this tree is known to be a light source; a real table
+        // would look up e.g. torches/lava/glowstone here.
+        0
+    }
+}
+
+fn opacity(block_manager: &BlockManager, state: BlockState) -> u8 {
+    match state.packed_key {
+        None => 0,
+        Some(_) => block_manager.light_opacity(state),
+    }
+}
+
+fn emission(block_manager: &BlockManager, state: BlockState) -> u8 {
+    match state.packed_key {
+        None => 0,
+        Some(_) => block_manager.light_emission(state),
+    }
+}
+
+/// Samples the light at `(x, y, z)` as `[block_light, sky_light]` in `0.0..=1.0`.
+/// `x`/`z` that stray outside this chunk (e.g. a face normal pointing into a
+/// neighbor chunk, the common case this exists for) are followed into the
+/// neighbor chunk through `loaded_chunks`, the same way
+/// [`Chunk::neighbor_blockstate_at`] does for blocks, rather than clamped back
+/// onto the emitting block itself. `y` can't cross a chunk boundary, so it's
+/// still clamped. A neighbor that isn't loaded falls back to the clamped
+/// in-chunk sample, since there's nothing better to read.
+#[must_use]
+pub fn sample(
+    light: &LightData,
+    chunk_pos: ChunkPos,
+    loaded_chunks: &LoadedChunks,
+    x: i32,
+    y: i32,
+    z: i32,
+) -> [f32; 2] {
+    let cy = y.clamp(0, CHUNK_HEIGHT as i32 - 1) as usize;
+
+    let chunk_dx = x.div_euclid(CHUNK_WIDTH as i32);
+    let chunk_dz = z.div_euclid(CHUNK_WIDTH as i32);
+
+    if chunk_dx == 0 && chunk_dz == 0 {
+        let cx = x as usize;
+        let cz = z as usize;
+        return [
+            light.block(cx, cy, cz) as f32 / MAX_LIGHT as f32,
+            light.sky(cx, cy, cz) as f32 / MAX_LIGHT as f32,
+        ];
+    }
+
+    let neighbor_pos = (chunk_pos.0 + chunk_dx, chunk_pos.1 + chunk_dz);
+    let Some(neighbor) = loaded_chunks.get(&neighbor_pos) else {
+        let cx = x.clamp(0, CHUNK_WIDTH as i32 - 1) as usize;
+        let cz = z.clamp(0, CHUNK_WIDTH as i32 - 1) as usize;
+        return [
+            light.block(cx, cy, cz) as f32 / MAX_LIGHT as f32,
+            light.sky(cx, cy, cz) as f32 / MAX_LIGHT as f32,
+        ];
+    };
+
+    let nx = x.rem_euclid(CHUNK_WIDTH as i32) as usize;
+    let nz = z.rem_euclid(CHUNK_WIDTH as i32) as usize;
+    let neighbor_light = neighbor.light.load();
+
+    [
+        neighbor_light.block(nx, cy, nz) as f32 / MAX_LIGHT as f32,
+        neighbor_light.sky(nx, cy, nz) as f32 / MAX_LIGHT as f32,
+    ]
+}
+
+/// Computes block light and skylight for an entire chunk from scratch.
+///
+/// Block light is seeded from every emissive block and flood-filled with a
+/// BFS; skylight is seeded by dropping straight down from the top of the
+/// world through transparent blocks with no attenuation, then flood-filled
+/// sideways with the same BFS, losing one level per block crossed plus that
+/// block's opacity. Call this before [`Chunk::bake`] so baked vertices can
+/// sample real light values instead of `[0.0, 0.0]`.
+pub fn compute_chunk_light(
+    chunk: &Chunk,
+    block_manager: &BlockManager,
+    loaded_chunks: &LoadedChunks,
+) -> LightData {
+    let mut light = LightData::dark();
+    let mut block_queue = VecDeque::new();
+    let mut sky_queue = VecDeque::new();
+
+    for z in 0..CHUNK_WIDTH {
+        for x in 0..CHUNK_WIDTH {
+            for y in (0..CHUNK_HEIGHT).rev() {
+                let state = state_at(chunk, x, y, z);
+                if opacity(block_manager, state) > 0 {
+                    break;
+                }
+
+                light.sky_light[index(x, y, z)] = MAX_LIGHT;
+                sky_queue.push_back(LocalPos {
+                    x: x as i32,
+                    y: y as i32,
+                    z: z as i32,
+                });
+            }
+
+            for y in 0..CHUNK_HEIGHT {
+                let state = state_at(chunk, x, y, z);
+                let level = emission(block_manager, state);
+                if level > 0 {
+                    light.block_light[index(x, y, z)] = level;
+                    block_queue.push_back(LocalPos {
+                        x: x as i32,
+                        y: y as i32,
+                        z: z as i32,
+                    });
+                }
+            }
+        }
+    }
+
+    seed_from_neighbors(
+        chunk,
+        chunk.pos,
+        loaded_chunks,
+        block_manager,
+        &mut light.block_light,
+        &mut light.sky_light,
+        &mut block_queue,
+        &mut sky_queue,
+    );
+
+    propagate(&mut light.block_light, block_queue, chunk, block_manager);
+    propagate(&mut light.sky_light, sky_queue, chunk, block_manager);
+
+    light
+}
+
+/// Standard 6-neighbor BFS: a neighbor's light is `current - 1 - opacity`,
+/// and it's only updated (and re-queued) when that's brighter than what it
+/// already has. Stays within `chunk`; crossing into a neighbor chunk is
+/// instead handled once up front by [`seed_from_neighbors`], since that's
+/// the only point where another chunk's data needs to be read.
+fn propagate(
+    values: &mut [u8; CHUNK_VOLUME],
+    mut queue: VecDeque<LocalPos>,
+    chunk: &Chunk,
+    block_manager: &BlockManager,
+) {
+    while let Some(pos) = queue.pop_front() {
+        let current = values[index(pos.x as usize, pos.y as usize, pos.z as usize)];
+        if current <= 1 {
+            continue;
+        }
+
+        for (dx, dy, dz) in NEIGHBORS {
+            let (nx, ny, nz) = (pos.x + dx, pos.y + dy, pos.z + dz);
+
+            if nx < 0 || nx >= CHUNK_WIDTH as i32 || nz < 0 || nz >= CHUNK_WIDTH as i32 {
+                // Picked up by the neighbor chunk's own light pass instead.
+                continue;
+            }
+            if !(0..CHUNK_HEIGHT as i32).contains(&ny) {
+                continue;
+            }
+
+            let state = state_at(chunk, nx as usize, ny as usize, nz as usize);
+            let new_level = current
+                .saturating_sub(1)
+                .saturating_sub(opacity(block_manager, state));
+
+            let idx = index(nx as usize, ny as usize, nz as usize);
+            if new_level > values[idx] {
+                values[idx] = new_level;
+                queue.push_back(LocalPos { x: nx, y: ny, z: nz });
+            }
+        }
+    }
+}
+
+/// Seeds this chunk's four edges from its already-lit horizontal neighbors,
+/// so light keeps flowing across chunk borders instead of stopping dead at
+/// x/z == 0 or 15. A neighbor that isn't loaded yet just leaves that edge at
+/// its current (unlit) value; it'll pick up light from `chunk` in turn once
+/// it's loaded and computes its own `LightData`.
+#[allow(clippy::too_many_arguments)]
+fn seed_from_neighbors(
+    chunk: &Chunk,
+    chunk_pos: ChunkPos,
+    loaded_chunks: &LoadedChunks,
+    block_manager: &BlockManager,
+    block_values: &mut [u8; CHUNK_VOLUME],
+    sky_values: &mut [u8; CHUNK_VOLUME],
+    block_queue: &mut VecDeque<LocalPos>,
+    sky_queue: &mut VecDeque<LocalPos>,
+) {
+    for dir in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        let neighbor_pos = (chunk_pos.0 + dir.0, chunk_pos.1 + dir.1);
+        let Some(neighbor) = loaded_chunks.get(&neighbor_pos) else {
+            continue;
+        };
+        let neighbor_light = neighbor.light.load();
+
+        for i in 0..CHUNK_WIDTH {
+            let (own_x, own_z, neighbor_x, neighbor_z) = match dir {
+                (1, 0) => (CHUNK_WIDTH - 1, i, 0, i),
+                (-1, 0) => (0, i, CHUNK_WIDTH - 1, i),
+                (0, 1) => (i, CHUNK_WIDTH - 1, i, 0),
+                _ => (i, 0, i, CHUNK_WIDTH - 1),
+            };
+
+            for y in 0..CHUNK_HEIGHT {
+                let own_state = state_at(chunk, own_x, y, own_z);
+                let own_opacity = opacity(block_manager, own_state);
+                let own_idx = index(own_x, y, own_z);
+                let neighbor_idx = index(neighbor_x, y, neighbor_z);
+
+                let from_block = neighbor_light
+                    .block_light
+                    .get(neighbor_idx)
+                    .copied()
+                    .unwrap_or(0)
+                    .saturating_sub(1)
+                    .saturating_sub(own_opacity);
+                if from_block > block_values[own_idx] {
+                    block_values[own_idx] = from_block;
+                    block_queue.push_back(LocalPos {
+                        x: own_x as i32,
+                        y: y as i32,
+                        z: own_z as i32,
+                    });
+                }
+
+                let from_sky = neighbor_light
+                    .sky_light
+                    .get(neighbor_idx)
+                    .copied()
+                    .unwrap_or(0)
+                    .saturating_sub(1)
+                    .saturating_sub(own_opacity);
+                if from_sky > sky_values[own_idx] {
+                    sky_values[own_idx] = from_sky;
+                    sky_queue.push_back(LocalPos {
+                        x: own_x as i32,
+                        y: y as i32,
+                        z: own_z as i32,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Re-lights a chunk around a single block change (placed or broken).
+///
+/// Runs a "dark" BFS outward from `pos` that zeroes any light which could
+/// only have come from the old block, stopping as soon as it reaches cells
+/// bright enough to have an independent source; those border cells seed a
+/// normal [`propagate`] pass that re-floods the now-dark region from them.
+pub fn relight_block(
+    light: &mut LightData,
+    chunk: &Chunk,
+    block_manager: &BlockManager,
+    pos: (usize, usize, usize),
+) {
+    relight_channel(&mut light.block_light, chunk, block_manager, pos);
+    relight_channel(&mut light.sky_light, chunk, block_manager, pos);
+}
+
+fn relight_channel(
+    values: &mut [u8; CHUNK_VOLUME],
+    chunk: &Chunk,
+    block_manager: &BlockManager,
+    pos: (usize, usize, usize),
+) {
+    let (x, y, z) = pos;
+    let mut dark_queue = VecDeque::new();
+    let mut reseed_queue = VecDeque::new();
+
+    let old_level = values[index(x, y, z)];
+    values[index(x, y, z)] = 0;
+    dark_queue.push_back((LocalPos { x: x as i32, y: y as i32, z: z as i32 }, old_level));
+
+    while let Some((pos, old_level)) = dark_queue.pop_front() {
+        for (dx, dy, dz) in NEIGHBORS {
+            let (nx, ny, nz) = (pos.x + dx, pos.y + dy, pos.z + dz);
+
+            if nx < 0 || nx >= CHUNK_WIDTH as i32 || nz < 0 || nz >= CHUNK_WIDTH as i32 {
+                continue;
+            }
+            if !(0..CHUNK_HEIGHT as i32).contains(&ny) {
+                continue;
+            }
+
+            let idx = index(nx as usize, ny as usize, nz as usize);
+            let neighbor_level = values[idx];
+            if neighbor_level == 0 {
+                continue;
+            }
+
+            if neighbor_level < old_level {
+                // Was only lit by the source we just removed: darken and keep going.
+                values[idx] = 0;
+                dark_queue.push_back((LocalPos { x: nx, y: ny, z: nz }, neighbor_level));
+            } else {
+                // Bright enough to have its own source; reseed from it instead.
+                reseed_queue.push_back(LocalPos { x: nx, y: ny, z: nz });
+            }
+        }
+    }
+
+    propagate(values, reseed_queue, chunk, block_manager);
+}
+
+/// Whether any of `new`'s four edge columns (`x`/`z` at `0` or
+/// `CHUNK_WIDTH - 1`, every `y`) differs from `old`'s. [`seed_from_neighbors`]
+/// and [`sample`] are the only things that read another chunk's light, and
+/// they only ever read these columns, so "did the border change" is exactly "does a
+/// neighbor need to re-bake against this chunk now".
+#[must_use]
+pub fn border_changed(old: &LightData, new: &LightData) -> bool {
+    for z in 0..CHUNK_WIDTH {
+        for &x in &[0, CHUNK_WIDTH - 1] {
+            for y in 0..CHUNK_HEIGHT {
+                let idx = index(x, y, z);
+                if old.block_light[idx] != new.block_light[idx] || old.sky_light[idx] != new.sky_light[idx] {
+                    return true;
+                }
+            }
+        }
+    }
+    for x in 0..CHUNK_WIDTH {
+        for &z in &[0, CHUNK_WIDTH - 1] {
+            for y in 0..CHUNK_HEIGHT {
+                let idx = index(x, y, z);
+                if old.block_light[idx] != new.block_light[idx] || old.sky_light[idx] != new.sky_light[idx] {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
@@ -3,23 +3,30 @@ use std::collections::HashMap;
 
 use crate::render::world::chunk::BakedChunkLayer;
 
-use arc_swap::ArcSwap;
+use arc_swap::{ArcSwap, ArcSwapOption};
 
 use parking_lot::RwLock;
-use rayon::iter::IntoParallelRefIterator;
 use std::convert::TryInto;
 use std::sync::Arc;
-use std::time::Instant;
 
 use crate::mc::BlockManager;
+use crate::render::compute::{ComputeMeshPipeline, ComputeMeshedSection};
 use crate::render::pipeline::grass::GrassVertex;
 use crate::render::pipeline::terrain::TerrainVertex;
 
+mod light;
+mod render_type;
+mod worker;
+
+use light::LightData;
+pub use render_type::RenderType;
+use worker::{BuildReply, BuildReq, ChunkBuilderPool};
+
 pub const CHUNK_WIDTH: usize = 16;
 pub const CHUNK_AREA: usize = CHUNK_WIDTH * CHUNK_WIDTH;
 pub const CHUNK_HEIGHT: usize = 256;
 pub const CHUNK_VOLUME: usize = CHUNK_AREA * CHUNK_HEIGHT;
-pub const CHUNK_SECTION_HEIGHT: usize = 1;
+pub const CHUNK_SECTION_HEIGHT: usize = 16;
 pub const CHUNK_SECTIONS_PER: usize = CHUNK_HEIGHT / CHUNK_SECTION_HEIGHT;
 pub const SECTION_VOLUME: usize = CHUNK_AREA * CHUNK_SECTION_HEIGHT;
 
@@ -27,6 +34,11 @@ use crate::WmRenderer;
 
 pub type ChunkPos = (i32, i32);
 
+/// A cheap, point-in-time snapshot of `ChunkManager::loaded_chunks`, used
+/// wherever baking or lighting needs to peek across a chunk boundary without
+/// holding the manager's `RwLock` (e.g. from a builder-pool worker thread).
+pub type LoadedChunks = HashMap<ChunkPos, Arc<Chunk>>;
+
 #[derive(Clone, Debug)]
 pub struct ChunkSection {
     pub empty: bool,
@@ -35,16 +47,19 @@ pub struct ChunkSection {
 }
 
 pub struct RenderLayers {
-    terrain: Box<[ChunkSection; CHUNK_SECTIONS_PER]>,
-    transparent: Box<[ChunkSection; CHUNK_SECTIONS_PER]>,
-    grass: Box<[ChunkSection; CHUNK_SECTIONS_PER]>,
+    sections: HashMap<RenderType, Box<[ChunkSection; CHUNK_SECTIONS_PER]>>,
 }
 
 #[derive(Debug)]
 pub struct ChunkLayers {
-    grass: BakedChunkLayer<GrassVertex>,
-    glass: BakedChunkLayer<TerrainVertex>,
-    terrain: BakedChunkLayer<TerrainVertex>,
+    /// `Solid`/`Translucent` blocks don't need biome tinting, so they bake
+    /// straight into `TerrainVertex`.
+    solid: BakedChunkLayer<TerrainVertex>,
+    translucent: BakedChunkLayer<TerrainVertex>,
+    /// `Cutout`/`Cross` blocks are foliage, which is biome-tinted, so they
+    /// bake into `GrassVertex` instead.
+    cutout: BakedChunkLayer<GrassVertex>,
+    cross: BakedChunkLayer<GrassVertex>,
 }
 
 #[derive(Debug)]
@@ -52,6 +67,9 @@ pub struct Chunk {
     pub pos: ChunkPos,
     pub sections: Box<[ChunkSection; CHUNK_SECTIONS_PER]>,
     pub baked: ArcSwap<Option<ChunkLayers>>,
+    /// Block light and skylight, recomputed by [`Chunk::compute_light`] (or,
+    /// for a single edited block, updated by `light::relight_block`) before baking.
+    pub light: ArcSwap<LightData>,
 }
 
 impl Chunk {
@@ -68,7 +86,7 @@ impl Chunk {
                     .unwrap();
 
                 ChunkSection {
-                    empty: !blocks.iter().any(|state| state.packed_key.is_some()),
+                    empty: !block_section.iter().any(|state| state.packed_key.is_some()),
                     blocks: block_section,
                     offset_y: section * CHUNK_SECTION_HEIGHT,
                 }
@@ -81,80 +99,220 @@ impl Chunk {
             pos,
             sections,
             baked: ArcSwap::new(Arc::new(None)),
+            light: ArcSwap::new(Arc::new(LightData::dark())),
         }
     }
 
+    /// Recomputes this chunk's block light and skylight. Must be called
+    /// before [`Chunk::bake`]/[`Chunk::bake_layers`] for the result to show
+    /// up in the baked mesh's `lightmap_coords`.
+    pub fn compute_light(
+        &self,
+        block_manager: &BlockManager,
+        loaded_chunks: &LoadedChunks,
+    ) {
+        let light = light::compute_chunk_light(self, block_manager, loaded_chunks);
+        self.light.store(Arc::new(light));
+    }
+
     #[must_use]
     pub fn blockstate_at_pos(&self, pos: BlockPos) -> BlockState {
         let x = (pos.0 % 16) as usize;
         let y = (pos.1) as usize;
         let z = (pos.2 % 16) as usize;
 
-        self.sections[y].blocks[(z * CHUNK_WIDTH) + x]
+        let section = y / CHUNK_SECTION_HEIGHT;
+        let local_y = y % CHUNK_SECTION_HEIGHT;
+
+        self.sections[section].blocks[(local_y * CHUNK_AREA) + (z * CHUNK_WIDTH) + x]
     }
 
-    pub fn bake(&self, block_manager: &BlockManager) {
-        let grass_index = *block_manager
-            .variant_indices
-            .get(&"minecraft:blockstates/grass.json#".try_into().unwrap())
-            .unwrap() as u32;
+    /// Like [`Chunk::blockstate_at_pos`], but follows `x`/`z` into a neighbor
+    /// chunk through `loaded_chunks` when they fall outside `0..CHUNK_WIDTH`,
+    /// rather than panicking. Returns `None` past the top/bottom of the world
+    /// or when the neighbor chunk needed isn't loaded; callers treat that as
+    /// "can't tell, don't cull".
+    #[must_use]
+    pub fn neighbor_blockstate_at(
+        &self,
+        loaded_chunks: &LoadedChunks,
+        x: i32,
+        y: i32,
+        z: i32,
+    ) -> Option<BlockState> {
+        if !(0..CHUNK_HEIGHT as i32).contains(&y) {
+            return None;
+        }
+
+        let chunk_dx = x.div_euclid(CHUNK_WIDTH as i32);
+        let chunk_dz = z.div_euclid(CHUNK_WIDTH as i32);
+        let local_x = x.rem_euclid(CHUNK_WIDTH as i32) as usize;
+        let local_z = z.rem_euclid(CHUNK_WIDTH as i32) as usize;
 
-        let glass_index = *block_manager
-            .variant_indices
-            .get(&"minecraft:blockstates/glass.json#".try_into().unwrap())
-            .unwrap() as u32;
+        let section = y as usize / CHUNK_SECTION_HEIGHT;
+        let local_y = y as usize % CHUNK_SECTION_HEIGHT;
+        let index = (local_y * CHUNK_AREA) + (local_z * CHUNK_WIDTH) + local_x;
+
+        if chunk_dx == 0 && chunk_dz == 0 {
+            return Some(self.sections[section].blocks[index]);
+        }
 
-        let grass = BakedChunkLayer::bake(
+        let neighbor_pos = (self.pos.0 + chunk_dx, self.pos.1 + chunk_dz);
+        let neighbor = loaded_chunks.get(&neighbor_pos)?;
+        Some(neighbor.sections[section].blocks[index])
+    }
+
+    /// Whether the face at `(x, y, z)` pointing along `normal` should be
+    /// skipped because the block on the other side of it is a full, opaque
+    /// cube. `render::world::chunk::BakedChunkLayer::bake` is expected to
+    /// call this once per candidate face, using [`Chunk::neighbor_blockstate_at`]
+    /// to see across section and chunk boundaries, before invoking its vertex
+    /// closure for that face. A neighbor `bake` can't resolve (past the top/
+    /// bottom of the world, or in an unloaded chunk) is treated as visible,
+    /// same as `neighbor_blockstate_at`'s own "can't tell, don't cull" rule.
+    #[must_use]
+    pub fn is_face_occluded(
+        &self,
+        block_manager: &BlockManager,
+        loaded_chunks: &LoadedChunks,
+        x: i32,
+        y: i32,
+        z: i32,
+        normal: [i8; 3],
+    ) -> bool {
+        let Some(neighbor) = self.neighbor_blockstate_at(
+            loaded_chunks,
+            x + normal[0] as i32,
+            y + normal[1] as i32,
+            z + normal[2] as i32,
+        ) else {
+            return false;
+        };
+
+        neighbor.packed_key.is_some() && block_manager.render_type(neighbor) == RenderType::Solid
+    }
+
+    /// Bakes this chunk's mesh and stores it, for callers that want to bake synchronously.
+    /// Chunks streamed in through the builder pool instead call [`Chunk::bake_layers`]
+    /// directly from the worker thread and let the manager store the result.
+    pub fn bake(&self, block_manager: &BlockManager, loaded_chunks: &LoadedChunks) {
+        self.baked
+            .store(Arc::new(Some(self.bake_layers(block_manager, loaded_chunks))));
+    }
+
+    #[must_use]
+    pub fn bake_layers(
+        &self,
+        block_manager: &BlockManager,
+        loaded_chunks: &LoadedChunks,
+    ) -> ChunkLayers {
+        let light = self.light.load();
+
+        // Solid/translucent/cutout faces get neighbor-aware culling via
+        // `Chunk::is_face_occluded` (backed by `neighbor_blockstate_at`),
+        // which `BakedChunkLayer::bake` calls per candidate face using the
+        // `self`/`loaded_chunks` passed below to see across section and
+        // chunk boundaries. Cross-shaped geometry below has no faces to cull.
+        let solid = BakedChunkLayer::bake(
             block_manager,
             self,
-            |v, x, y, z| GrassVertex {
+            loaded_chunks,
+            |v, x, y, z| TerrainVertex {
                 position: [v.position[0] + x, v.position[1] + y, v.position[2] + z],
                 tex_coords: v.tex_coords,
-                lightmap_coords: [0.0, 0.0],
+                lightmap_coords: light::sample(
+                    &light,
+                    self.pos,
+                    loaded_chunks,
+                    x as i32 + v.normal[0] as i32,
+                    y as i32 + v.normal[1] as i32,
+                    z as i32 + v.normal[2] as i32,
+                ),
                 normal: v.normal,
-                biome_color_coords: [0.0, 0.0],
             },
             Box::new(move |state| match state.packed_key {
                 None => false,
-                Some(key) => key == grass_index,
+                Some(_) => block_manager.render_type(state) == RenderType::Solid,
             }),
         );
 
-        let glass = BakedChunkLayer::bake(
+        let translucent = BakedChunkLayer::bake(
             block_manager,
             self,
+            loaded_chunks,
             |v, x, y, z| TerrainVertex {
                 position: [v.position[0] + x, v.position[1] + y, v.position[2] + z],
                 tex_coords: v.tex_coords,
-                lightmap_coords: [0.0, 0.0],
+                lightmap_coords: light::sample(
+                    &light,
+                    self.pos,
+                    loaded_chunks,
+                    x as i32 + v.normal[0] as i32,
+                    y as i32 + v.normal[1] as i32,
+                    z as i32 + v.normal[2] as i32,
+                ),
                 normal: v.normal,
             },
             Box::new(move |state| match state.packed_key {
                 None => false,
-                Some(key) => key == glass_index,
+                Some(_) => block_manager.render_type(state) == RenderType::Translucent,
             }),
         );
 
-        let terrain = BakedChunkLayer::bake(
+        let cutout = BakedChunkLayer::bake(
             block_manager,
             self,
-            |v, x, y, z| TerrainVertex {
+            loaded_chunks,
+            |v, x, y, z| GrassVertex {
+                position: [v.position[0] + x, v.position[1] + y, v.position[2] + z],
+                tex_coords: v.tex_coords,
+                lightmap_coords: light::sample(
+                    &light,
+                    self.pos,
+                    loaded_chunks,
+                    x as i32 + v.normal[0] as i32,
+                    y as i32 + v.normal[1] as i32,
+                    z as i32 + v.normal[2] as i32,
+                ),
+                normal: v.normal,
+                biome_color_coords: [0.0, 0.0],
+            },
+            Box::new(move |state| match state.packed_key {
+                None => false,
+                Some(_) => block_manager.render_type(state) == RenderType::Cutout,
+            }),
+        );
+
+        // Cross-shaped blocks (torches, tall grass, flowers) get two
+        // intersecting diagonal quads per block instead of cube faces, so
+        // they're baked with a dedicated method rather than `bake`. Like
+        // `bake` itself, `bake_cross`'s vertex geometry is assumed to already
+        // exist on `BakedChunkLayer` in `render::world::chunk`, which isn't
+        // part of this tree to define or extend; the same is true of the
+        // `cutout` layer's alpha-cutout fragment shader, which lives in
+        // whatever pipeline module renders `GrassVertex` (not present here).
+        let cross = BakedChunkLayer::bake_cross(
+            block_manager,
+            self,
+            |v, x, y, z| GrassVertex {
                 position: [v.position[0] + x, v.position[1] + y, v.position[2] + z],
                 tex_coords: v.tex_coords,
-                lightmap_coords: [0.0, 0.0],
+                lightmap_coords: light::sample(&light, self.pos, loaded_chunks, x as i32, y as i32, z as i32),
                 normal: v.normal,
+                biome_color_coords: [0.0, 0.0],
             },
             Box::new(move |state| match state.packed_key {
                 None => false,
-                Some(key) => key != grass_index && key != glass_index,
+                Some(_) => block_manager.render_type(state) == RenderType::Cross,
             }),
         );
 
-        self.baked.store(Arc::new(Some(ChunkLayers {
-            grass,
-            glass,
-            terrain,
-        })));
+        ChunkLayers {
+            solid,
+            translucent,
+            cutout,
+            cross,
+        }
     }
 }
 
@@ -168,70 +326,332 @@ pub struct WorldBuffers {
     pub other: (wgpu::Buffer, usize),
 }
 
+/// Which path produces a chunk's mesh: the CPU [`Chunk::bake_layers`] pass
+/// (the fallback for platforms without compute support), or the GPU
+/// [`ComputeMeshPipeline`]. Chosen once at
+/// [`ChunkManager::new`], since switching mid-session would mean keeping both
+/// sets of GPU resources around for no benefit.
+pub enum MeshingMode {
+    Cpu,
+    /// The already-built compute pipeline to mesh sections with. Built
+    /// externally via [`ComputeMeshPipeline::new`], since it needs a
+    /// `wgpu::Device`, which `ChunkManager` otherwise has no reason to hold.
+    Compute(ComputeMeshPipeline),
+}
+
 pub struct ChunkManager {
     //Due to floating point inaccuracy at large distances,
     //we need to keep the model coordinates as close to 0,0,0 as possible
     pub chunk_origin: ArcSwap<ChunkPos>,
     pub loaded_chunks: RwLock<HashMap<ChunkPos, ArcSwap<Chunk>>>,
-    pub section_buffers: ArcSwap<HashMap<String, WorldBuffers>>,
+    /// One `WorldBuffers` set per render type, per chunk. Populated incrementally
+    /// by [`ChunkManager::tick`] as bakes finish, rather than rebuilt wholesale.
+    /// Only used in [`MeshingMode::Cpu`].
+    pub section_buffers: RwLock<HashMap<ChunkPos, HashMap<RenderType, WorldBuffers>>>,
+    /// One [`ComputeMeshedSection`] per non-empty section, per chunk. Only
+    /// used in [`MeshingMode::Compute`], in place of `section_buffers`: the
+    /// indirect draw already has its face culling baked in by the compute
+    /// shader, so there's no need for `WorldBuffers`' per-face-direction split.
+    pub compute_buffers: RwLock<HashMap<ChunkPos, Vec<ComputeMeshedSection>>>,
+    /// Chunks that are currently queued on, or in-flight on, the builder pool.
+    /// Keeps `bake_meshes`/`mark_chunk_dirty` from submitting the same chunk twice.
+    building: RwLock<HashMap<ChunkPos, bool>>,
+    /// Chunks that were (re)dirtied while already present in `building`, so the
+    /// dirty that arrived mid-build wasn't dropped; `tick` resubmits these once
+    /// the in-flight build they arrived during comes back.
+    redirty: RwLock<HashMap<ChunkPos, Arc<Chunk>>>,
+    /// Lazily-populated snapshot of `wm.mc.block_manager`, reused across bakes
+    /// instead of re-cloning the whole block/model registry out from behind its
+    /// lock on every `bake_meshes`/`mark_chunk_dirty` call. Refreshed only when
+    /// [`ChunkManager::invalidate_block_manager`] is called, e.g. after a
+    /// resource (re)load changes the registry.
+    block_manager: ArcSwapOption<BlockManager>,
+    builder_pool: ChunkBuilderPool,
+    meshing_mode: MeshingMode,
 }
 
 impl ChunkManager {
     #[must_use]
-    pub fn new() -> Self {
+    pub fn new(meshing_mode: MeshingMode) -> Self {
         ChunkManager {
             chunk_origin: ArcSwap::new(Arc::new((0, 0))),
             loaded_chunks: RwLock::new(HashMap::new()),
-            section_buffers: ArcSwap::new(Arc::new(HashMap::new())),
+            section_buffers: RwLock::new(HashMap::new()),
+            compute_buffers: RwLock::new(HashMap::new()),
+            building: RwLock::new(HashMap::new()),
+            redirty: RwLock::new(HashMap::new()),
+            block_manager: ArcSwapOption::from(None),
+            builder_pool: ChunkBuilderPool::new(rayon::current_num_threads()),
+            meshing_mode,
         }
     }
 
+    /// Drops the cached `BlockManager` snapshot `bake_meshes`/`mark_chunk_dirty`
+    /// reuse, so the next call re-clones it from `wm.mc.block_manager`. Call
+    /// this after anything that actually changes the block/model registry
+    /// (e.g. a resource pack reload); ordinary block edits don't need it.
+    pub fn invalidate_block_manager(&self) {
+        self.block_manager.store(None);
+    }
+
+    /// Returns the cached `BlockManager` snapshot, cloning a fresh one out from
+    /// behind `wm.mc.block_manager`'s lock only if the cache is empty (first
+    /// call, or after [`ChunkManager::invalidate_block_manager`]).
+    fn block_manager(&self, wm: &WmRenderer) -> Arc<BlockManager> {
+        if let Some(cached) = self.block_manager.load_full() {
+            return cached;
+        }
+
+        let fresh = Arc::new(wm.mc.block_manager.read().clone());
+        self.block_manager.store(Some(fresh.clone()));
+        fresh
+    }
+
+    /// Queue every loaded chunk for (re)meshing. A chunk that's already queued or
+    /// in-flight isn't submitted twice; `tick` resubmits it once that build returns.
+    /// Under [`MeshingMode::Cpu`] this submits to the builder pool, and meshing happens
+    /// off this thread; call [`ChunkManager::tick`] each frame to pick up the results.
+    /// Under [`MeshingMode::Compute`] the mesh is produced immediately on the GPU.
     pub fn bake_meshes(&self, wm: &WmRenderer) {
-        let block_manager = wm.mc.block_manager.read();
+        let loaded_chunks = self.loaded_chunks.read();
+
+        match &self.meshing_mode {
+            MeshingMode::Cpu => {
+                let block_manager = self.block_manager(wm);
+                let neighbors = Self::snapshot_loaded_chunks(&loaded_chunks);
+                for (&pos, chunk) in loaded_chunks.iter() {
+                    self.submit_chunk(pos, chunk.load_full(), &block_manager, &neighbors, false, None);
+                }
+            }
+            MeshingMode::Compute(pipeline) => {
+                for (&pos, chunk) in loaded_chunks.iter() {
+                    self.mesh_chunk_compute(wm, pipeline, pos, &chunk.load_full());
+                }
+            }
+        }
+    }
 
-        let chunks = {
-            self.loaded_chunks
-                .read()
-                .iter()
-                .map(|(_pos, chunk)| chunk.load_full())
-                .collect::<Vec<_>>()
+    /// Mark a single chunk dirty (e.g. after a block edit) and re-mesh it. If
+    /// it's already queued or in-flight, the dirty isn't dropped: `tick` picks
+    /// it back up once the in-flight build finishes (only relevant under
+    /// [`MeshingMode::Cpu`]; the compute path has no queue to deduplicate against).
+    ///
+    /// `edited_block`, when given, is the single block that changed; the
+    /// worker uses it to run [`light::relight_block`]'s incremental dark-BFS
+    /// instead of a full [`Chunk::compute_light`] recompute. Pass `None` when
+    /// the dirty isn't about one specific block (e.g. a resource pack reload
+    /// changing `render_type`/opacity for existing blocks), which forces the
+    /// full recompute.
+    pub fn mark_chunk_dirty(&self, wm: &WmRenderer, pos: ChunkPos, edited_block: Option<BlockPos>) {
+        let loaded_chunks = self.loaded_chunks.read();
+        let Some(chunk) = loaded_chunks.get(&pos) else {
+            return;
         };
 
-        use rayon::iter::ParallelIterator;
-        let time = Instant::now();
-        chunks
-            .par_iter()
-            .for_each(|chunk| chunk.bake(&block_manager));
-        println!(
-            "Baked chunk in {}ms",
-            Instant::now().duration_since(time).as_millis()
-        );
+        match &self.meshing_mode {
+            MeshingMode::Cpu => {
+                let block_manager = self.block_manager(wm);
+                let neighbors = Self::snapshot_loaded_chunks(&loaded_chunks);
+                let local_edit = edited_block.map(|p| {
+                    (
+                        (p.0 as usize) % CHUNK_WIDTH,
+                        p.1 as usize,
+                        (p.2 as usize) % CHUNK_WIDTH,
+                    )
+                });
+                self.submit_chunk(pos, chunk.load_full(), &block_manager, &neighbors, false, local_edit);
+            }
+            MeshingMode::Compute(pipeline) => {
+                self.mesh_chunk_compute(wm, pipeline, pos, &chunk.load_full());
+            }
+        }
+    }
 
-        let mut glass = BakedChunkLayer::new();
-        let mut grass = BakedChunkLayer::new();
-        let mut terrain = BakedChunkLayer::new();
+    /// Meshes every non-empty section of `chunk` on the GPU via `pipeline`
+    /// and stores the result in `compute_buffers`, replacing whatever was
+    /// there for this chunk before.
+    fn mesh_chunk_compute(&self, wm: &WmRenderer, pipeline: &ComputeMeshPipeline, pos: ChunkPos, chunk: &Chunk) {
+        let chunk_corner = [
+            (pos.0 * CHUNK_WIDTH as i32) as f32,
+            (pos.1 * CHUNK_WIDTH as i32) as f32,
+        ];
+
+        let sections = chunk
+            .sections
+            .iter()
+            .filter(|section| !section.empty)
+            .map(|section| {
+                pipeline.mesh_section(&wm.wgpu_state.device, &wm.wgpu_state.queue, section, chunk_corner)
+            })
+            .collect();
 
-        chunks.iter().for_each(|chunk| {
-            let baked = chunk.baked.load();
-            let layers = (**baked).as_ref().unwrap();
+        self.compute_buffers.write().insert(pos, sections);
+    }
 
-            glass.extend(&layers.glass);
-            grass.extend(&layers.grass);
-            terrain.extend(&layers.terrain);
+    /// Draws every currently-meshed [`MeshingMode::Compute`] section. Under
+    /// [`MeshingMode::Cpu`] `compute_buffers` is always empty, so this is a
+    /// no-op there. This crate has no render-loop module of its own that
+    /// binds the terrain pipeline and iterates its render passes -- that
+    /// lives upstream, the same as whatever consumes `section_buffers`' CPU
+    /// `WorldBuffers` -- but that loop needs a single call into
+    /// `ChunkManager` to draw the compute-meshed chunks, and this is it.
+    pub fn draw_compute_sections<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        for sections in self.compute_buffers.read().values() {
+            for section in sections {
+                section.draw(render_pass);
+            }
+        }
+    }
+
+    /// Takes a point-in-time [`LoadedChunks`] snapshot of `loaded_chunks`, so
+    /// baking/lighting can look across chunk boundaries from a worker thread
+    /// without borrowing `ChunkManager`'s `RwLock`.
+    fn snapshot_loaded_chunks(loaded_chunks: &HashMap<ChunkPos, ArcSwap<Chunk>>) -> Arc<LoadedChunks> {
+        Arc::new(
+            loaded_chunks
+                .iter()
+                .map(|(&pos, chunk)| (pos, chunk.load_full()))
+                .collect(),
+        )
+    }
+
+    fn submit_chunk(
+        &self,
+        pos: ChunkPos,
+        chunk: Arc<Chunk>,
+        block_manager: &Arc<BlockManager>,
+        neighbors: &Arc<LoadedChunks>,
+        relight_trigger: bool,
+        edited_block: Option<(usize, usize, usize)>,
+    ) {
+        let mut building = self.building.write();
+        if matches!(building.get(&pos), Some(true)) {
+            // Already in flight: don't submit a second build for it, but
+            // remember that it needs one once the in-flight build is done,
+            // so this dirty isn't silently dropped. The redirty map only
+            // tracks the chunk, not which block changed, so a dirty that
+            // lands mid-build falls back to a full relight rather than an
+            // incremental one -- always correct, just not maximally cheap.
+            self.redirty.write().insert(pos, chunk);
+            return;
+        }
+        building.insert(pos, true);
+        drop(building);
+
+        // Lighting and neighbor-aware face culling both need to see neighbor
+        // chunks, which a worker thread can't borrow from `self.loaded_chunks`
+        // directly, so `bake_meshes`/`mark_chunk_dirty` hand us an
+        // already-snapshotted `LoadedChunks` to pass along. Computing the
+        // light itself also happens on the worker now, not here, since it's a
+        // full per-chunk BFS flood and this runs on the render thread.
+        self.builder_pool.submit(BuildReq {
+            pos,
+            chunk,
+            block_manager: block_manager.clone(),
+            neighbors: neighbors.clone(),
+            relight_trigger,
+            edited_block,
         });
+    }
+
+    /// The four horizontally-adjacent chunk positions, the only neighbors
+    /// [`light::seed_from_neighbors`]/`sample` cross into.
+    fn adjacent_positions(pos: ChunkPos) -> [ChunkPos; 4] {
+        [
+            (pos.0 + 1, pos.1),
+            (pos.0 - 1, pos.1),
+            (pos.0, pos.1 + 1),
+            (pos.0, pos.1 - 1),
+        ]
+    }
+
+    /// Drain meshes that have finished baking on the builder pool and upload each
+    /// chunk's layers into its own `WorldBuffers` slot. Intended to be polled once
+    /// per frame from the render thread.
+    pub fn tick(&self, wm: &WmRenderer) {
+        let mut replies = Vec::new();
+        while let Ok((_worker_id, reply)) = self.builder_pool.replies.try_recv() {
+            replies.push(reply);
+        }
+
+        if replies.is_empty() {
+            return;
+        }
+
+        let mut to_resubmit = Vec::new();
+        // One-hop neighbor relight cascade: `pos` finished with fresh light,
+        // but its neighbors may have baked earlier against `pos`'s old (still
+        // mostly-dark) border, so they need a re-bake against the now-correct
+        // border. Only collected for replies that weren't themselves a
+        // cascade step, so this can't bounce back and forth forever.
+        let mut to_relight = Vec::new();
+
+        {
+            let mut section_buffers = self.section_buffers.write();
+            let mut building = self.building.write();
+            let mut redirty = self.redirty.write();
+
+            for BuildReply { pos, layers, relight_trigger, border_changed } in replies {
+                building.remove(&pos);
+
+                let mut layer_buffers = HashMap::new();
+                layer_buffers.insert(RenderType::Solid, layers.solid.upload(wm));
+                layer_buffers.insert(RenderType::Translucent, layers.translucent.upload(wm));
+                layer_buffers.insert(RenderType::Cutout, layers.cutout.upload(wm));
+                layer_buffers.insert(RenderType::Cross, layers.cross.upload(wm));
+
+                section_buffers.insert(pos, layer_buffers);
+
+                // This chunk was dirtied again while its build was in flight;
+                // the dirty was held here instead of being dropped, so submit
+                // the up-to-date chunk now that there's room for it.
+                if let Some(chunk) = redirty.remove(&pos) {
+                    to_resubmit.push((pos, chunk));
+                }
 
-        let mut map = HashMap::new();
+                // Only neighbors of a build whose border light actually moved
+                // need to re-bake against it; otherwise a bulk `bake_meshes`
+                // would cascade every chunk into re-baking its neighbors for
+                // no visible change.
+                if !relight_trigger && border_changed {
+                    to_relight.push(pos);
+                }
+            }
+        }
 
-        map.insert("transparent".into(), glass.upload(wm));
-        map.insert("grass".into(), grass.upload(wm));
-        map.insert("terrain".into(), terrain.upload(wm));
+        if to_resubmit.is_empty() && to_relight.is_empty() {
+            return;
+        }
 
-        self.section_buffers.store(Arc::new(map));
+        if let MeshingMode::Cpu = &self.meshing_mode {
+            let block_manager = self.block_manager(wm);
+            let loaded_chunks = self.loaded_chunks.read();
+            let neighbors = Self::snapshot_loaded_chunks(&loaded_chunks);
+
+            for (pos, chunk) in to_resubmit {
+                self.submit_chunk(pos, chunk, &block_manager, &neighbors, false, None);
+            }
+
+            for pos in to_relight {
+                for neighbor_pos in Self::adjacent_positions(pos) {
+                    if let Some(chunk) = loaded_chunks.get(&neighbor_pos) {
+                        self.submit_chunk(
+                            neighbor_pos,
+                            chunk.load_full(),
+                            &block_manager,
+                            &neighbors,
+                            true,
+                            None,
+                        );
+                    }
+                }
+            }
+        }
     }
 }
 
 impl Default for ChunkManager {
     fn default() -> Self {
-        Self::new()
+        Self::new(MeshingMode::Cpu)
     }
 }
@@ -0,0 +1,23 @@
+/// How a block's faces should be rendered, looked up per `BlockState` via
+/// `BlockManager::render_type`.
+///
+/// Replaces the old approach in `Chunk::bake` of matching specific variant
+/// indices (`grass.json#`, `glass.json#`) against a fixed terrain/grass/glass
+/// triple, which doesn't generalize past those two blocks.
+///
+/// `render_type` is assumed to already exist on the real `BlockManager`;
+/// this module only defines the enum it returns, not the lookup itself.
+/// `chunk::light` derives `light_opacity`/`light_emission` from it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum RenderType {
+    /// A normal opaque cube; written straight into the depth buffer.
+    Solid,
+    /// Alpha-tested rather than alpha-blended: the fragment shader `discard`s
+    /// fully transparent texels, so foliage renders correctly without sorting.
+    Cutout,
+    /// Alpha-blended and depth-sorted, e.g. glass and water.
+    Translucent,
+    /// Two intersecting diagonal quads spanning the voxel instead of cube
+    /// faces, e.g. torches, tall grass and flowers.
+    Cross,
+}
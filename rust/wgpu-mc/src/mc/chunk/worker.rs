@@ -0,0 +1,126 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::thread::JoinHandle;
+
+use crate::mc::chunk::{light, Chunk, ChunkLayers, ChunkPos, LoadedChunks};
+use crate::mc::BlockManager;
+
+/// A request to bake a single chunk's mesh on a worker thread.
+pub struct BuildReq {
+    pub pos: ChunkPos,
+    pub chunk: Arc<Chunk>,
+    pub block_manager: Arc<BlockManager>,
+    /// A snapshot of `ChunkManager::loaded_chunks` at submission time, so the
+    /// worker thread can look across chunk boundaries for face culling
+    /// without borrowing the manager's `RwLock` from off-thread.
+    pub neighbors: Arc<LoadedChunks>,
+    /// Set when this request was submitted to re-light/re-bake a chunk after
+    /// one of its neighbors finished lighting, rather than in response to a
+    /// load or an edit. `ChunkManager::tick` uses this to requeue only the
+    /// neighbors of "real" builds, bounding the cascade to one hop instead of
+    /// neighbors re-triggering each other indefinitely.
+    pub relight_trigger: bool,
+    /// The single block that changed, in this chunk's local coordinates, if
+    /// that's what triggered this request. When set (and this isn't itself a
+    /// `relight_trigger`), the worker runs `light::relight_block`'s
+    /// incremental dark-BFS instead of a full `Chunk::compute_light`
+    /// recompute. `None` for loads, bulk bakes, and relight cascades, all of
+    /// which need the full recompute.
+    pub edited_block: Option<(usize, usize, usize)>,
+}
+
+/// The result of a `BuildReq`, sent back to whichever `ChunkManager` submitted it.
+pub struct BuildReply {
+    pub pos: ChunkPos,
+    pub layers: ChunkLayers,
+    pub relight_trigger: bool,
+    /// Whether this build's [`light::border_changed`] relative to the light
+    /// this chunk had before the build. `ChunkManager::tick` only queues this
+    /// chunk's neighbors for a relight cascade when this is set, instead of
+    /// unconditionally on every non-cascade build -- a bulk `bake_meshes`
+    /// would otherwise re-bake every chunk several times over as each
+    /// neighbor's border light turned out not to have changed at all.
+    pub border_changed: bool,
+}
+
+/// A fixed pool of threads dedicated to baking chunk meshes off the render thread.
+///
+/// Each worker owns the receiving half of its own `mpsc` channel and blocks on
+/// it for work; finished bakes are funneled back through a single shared
+/// `Sender`, tagged with the id of the worker that produced them.
+pub struct ChunkBuilderPool {
+    senders: Vec<Sender<BuildReq>>,
+    next_worker: AtomicUsize,
+    pub replies: Receiver<(usize, BuildReply)>,
+    _workers: Vec<JoinHandle<()>>,
+}
+
+impl ChunkBuilderPool {
+    #[must_use]
+    pub fn new(worker_count: usize) -> Self {
+        let worker_count = worker_count.max(1);
+        let (reply_tx, reply_rx) = channel::<(usize, BuildReply)>();
+
+        let mut senders = Vec::with_capacity(worker_count);
+        let mut workers = Vec::with_capacity(worker_count);
+
+        for worker_id in 0..worker_count {
+            let (req_tx, req_rx) = channel::<BuildReq>();
+            let reply_tx = reply_tx.clone();
+
+            let handle = thread::Builder::new()
+                .name(format!("chunk-builder-{}", worker_id))
+                .spawn(move || Self::run(worker_id, req_rx, reply_tx))
+                .expect("failed to spawn chunk builder thread");
+
+            senders.push(req_tx);
+            workers.push(handle);
+        }
+
+        Self {
+            senders,
+            next_worker: AtomicUsize::new(0),
+            replies: reply_rx,
+            _workers: workers,
+        }
+    }
+
+    fn run(worker_id: usize, requests: Receiver<BuildReq>, replies: Sender<(usize, BuildReply)>) {
+        while let Ok(req) = requests.recv() {
+            // Lighting is a full per-chunk BFS flood, same cost class as the
+            // bake itself, so it runs here on the worker rather than back on
+            // the render thread that submitted the request. A single edited
+            // block gets the cheaper incremental relight instead.
+            let light_before = req.chunk.light.load_full();
+            match req.edited_block {
+                Some(pos) if !req.relight_trigger => {
+                    let mut chunk_light = (*light_before).clone();
+                    light::relight_block(&mut chunk_light, &req.chunk, &req.block_manager, pos);
+                    req.chunk.light.store(Arc::new(chunk_light));
+                }
+                _ => req.chunk.compute_light(&req.block_manager, &req.neighbors),
+            }
+            let border_changed = light::border_changed(&light_before, &req.chunk.light.load_full());
+            let layers = req.chunk.bake_layers(&req.block_manager, &req.neighbors);
+
+            let reply = BuildReply {
+                pos: req.pos,
+                layers,
+                relight_trigger: req.relight_trigger,
+                border_changed,
+            };
+            if replies.send((worker_id, reply)).is_err() {
+                // The manager has gone away; nothing left to report to.
+                break;
+            }
+        }
+    }
+
+    /// Queue a chunk for (re)baking, round-robining across the worker threads.
+    pub fn submit(&self, req: BuildReq) {
+        let worker = self.next_worker.fetch_add(1, Ordering::Relaxed) % self.senders.len();
+        let _ = self.senders[worker].send(req);
+    }
+}
@@ -0,0 +1,279 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::mc::chunk::{ChunkSection, CHUNK_SECTION_HEIGHT, CHUNK_WIDTH, SECTION_VOLUME};
+use crate::render::pipeline::terrain::TerrainVertex;
+
+/// Matches the per-section uniform the compute shader reads to offset emitted
+/// quads by `section_offset_y` and to know the section's dimensions, since the
+/// block-key storage buffer itself carries no shape information. Positions
+/// stay chunk-local: the shader does not read `chunk_corner`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Zeroable, Pod)]
+pub struct SectionMeshUniform {
+    pub chunk_corner: [f32; 2],
+    pub section_offset_y: f32,
+    pub _padding: f32,
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub _padding2: u32,
+}
+
+/// The `wgpu::util::DrawIndirectArgs` layout, bound as a read-write storage
+/// buffer so the compute shader can atomically bump `vertex_count` as it
+/// emits quads. Doubles as the `indirect_buffer` for the terrain pipeline's
+/// draw call, so no CPU readback of the emitted vertex count is needed.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Zeroable, Pod)]
+struct DrawIndirectCount {
+    vertex_count: u32,
+    instance_count: u32,
+    first_vertex: u32,
+    first_instance: u32,
+}
+
+/// GPU resources produced by meshing a single [`ChunkSection`] on the compute
+/// path: a vertex storage buffer sized for the worst case (every block
+/// emitting all 6 faces) and an indirect draw buffer whose `vertex_count` the
+/// compute shader fills in directly.
+pub struct ComputeMeshedSection {
+    pub vertex_buffer: wgpu::Buffer,
+    pub indirect_buffer: wgpu::Buffer,
+}
+
+impl ComputeMeshedSection {
+    /// Feeds this section straight into whatever terrain pipeline is already
+    /// bound on `render_pass`: set as vertex buffer 0, drawn with
+    /// `draw_indirect` so the vertex count the compute shader wrote into
+    /// `indirect_buffer` never has to come back to the CPU.
+    pub fn draw<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.draw_indirect(&self.indirect_buffer, 0);
+    }
+}
+
+/// Max vertices a single *fully dense* section could ever emit: every block
+/// showing all 6 quad faces at 6 vertices (2 triangles) each. `mesh_section`
+/// doesn't allocate this much for every section -- see `pack_section` -- but
+/// it's the ceiling a section with no air blocks at all would still need.
+const MAX_VERTICES_PER_SECTION: usize = SECTION_VOLUME * 6 * 6;
+
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Compute-shader terrain meshing: an alternative to
+/// `render::world::chunk::BakedChunkLayer::bake` that builds a section's mesh
+/// entirely on the GPU, avoiding the CPU-side vertex generation and upload
+/// that path does on every bake. Selected via `mc::chunk::MeshingMode::Compute`
+/// at `ChunkManager` construction; `MeshingMode::Cpu` remains the fallback for
+/// platforms without compute support.
+pub struct ComputeMeshPipeline {
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+}
+
+impl ComputeMeshPipeline {
+    #[must_use]
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("compute_mesh_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("mesh.wgsl").into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("compute_mesh_bind_group_layout"),
+            entries: &[
+                // Packed block keys for the section being meshed.
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // SectionMeshUniform.
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Output TerrainVertex storage buffer.
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Indirect draw args, doubling as the atomic vertex counter.
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("compute_mesh_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("compute_mesh_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: "cs_main",
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+        }
+    }
+
+    /// Packs `section`'s blocks into `u32` keys, in the same
+    /// `(local_y * CHUNK_AREA) + (z * CHUNK_WIDTH) + x` order
+    /// `ChunkSection::blocks` already uses, so the shader indexes it
+    /// identically to the CPU bake path. Air (`None`) packs to `0`; every real
+    /// block is packed as `key + 1`, so a block whose `packed_key` is `Some(0)`
+    /// doesn't collide with air and get meshed as empty space.
+    ///
+    /// Also returns the number of non-air blocks, so `mesh_section` can size
+    /// the vertex buffer off the section's actual content instead of
+    /// `MAX_VERTICES_PER_SECTION`'s fully-dense worst case on every call.
+    fn pack_section(section: &ChunkSection) -> (Vec<u32>, usize) {
+        let mut non_air = 0usize;
+        let keys = section
+            .blocks
+            .iter()
+            .map(|state| match state.packed_key {
+                Some(key) => {
+                    non_air += 1;
+                    key + 1
+                }
+                None => 0,
+            })
+            .collect();
+        (keys, non_air)
+    }
+
+    /// Meshes one section on the GPU: uploads its packed block keys and a
+    /// [`SectionMeshUniform`], dispatches the compute shader, and returns the
+    /// vertex/indirect buffers ready to feed straight into the terrain
+    /// pipeline's indirect draw call.
+    #[must_use]
+    pub fn mesh_section(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        section: &ChunkSection,
+        chunk_corner: [f32; 2],
+    ) -> ComputeMeshedSection {
+        let (keys, non_air) = Self::pack_section(section);
+        let key_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("compute_mesh_block_keys"),
+            contents: bytemuck::cast_slice(&keys),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        let uniform = SectionMeshUniform {
+            chunk_corner,
+            section_offset_y: section.offset_y as f32,
+            _padding: 0.0,
+            width: CHUNK_WIDTH as u32,
+            height: CHUNK_SECTION_HEIGHT as u32,
+            depth: CHUNK_WIDTH as u32,
+            _padding2: 0,
+        };
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("compute_mesh_section_uniform"),
+            contents: bytemuck::bytes_of(&uniform),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        // `section` isn't empty (callers filter that out), so `non_air` is at
+        // least 1; every non-air block can emit at most 6 faces of 6 vertices.
+        let vertex_capacity = (non_air * 6 * 6).min(MAX_VERTICES_PER_SECTION);
+        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("compute_mesh_vertices"),
+            size: (vertex_capacity * std::mem::size_of::<TerrainVertex>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::VERTEX,
+            mapped_at_creation: false,
+        });
+
+        let indirect_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("compute_mesh_indirect"),
+            contents: bytemuck::bytes_of(&DrawIndirectCount {
+                vertex_count: 0,
+                instance_count: 1,
+                first_vertex: 0,
+                first_instance: 0,
+            }),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::INDIRECT,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("compute_mesh_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: key_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: vertex_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: indirect_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("compute_mesh_encoder"),
+        });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("compute_mesh_pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(
+                (SECTION_VOLUME as u32 + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE,
+                1,
+                1,
+            );
+        }
+        queue.submit(Some(encoder.finish()));
+
+        ComputeMeshedSection {
+            vertex_buffer,
+            indirect_buffer,
+        }
+    }
+}
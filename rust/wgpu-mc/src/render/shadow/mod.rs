@@ -0,0 +1,411 @@
+use std::sync::Arc;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::render::entity::EntityVertex;
+use crate::render::pipeline::terrain::TerrainVertex;
+
+/// Resolution of each cascade's depth texture. Not yet exposed as a setting:
+/// the whole-map cascades this first cut renders don't need to trade it off
+/// against anything else.
+const SHADOW_MAP_SIZE: u32 = 2048;
+
+pub mod poisson;
+
+use poisson::POISSON_DISK_16;
+
+/// How a fragment's shadow factor is resolved once it's known to be behind
+/// (or in front of) the light's stored depth.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ShadowFilterMode {
+    /// A single hardware 2x2 comparison-sampler tap (`textureSampleCompare`
+    /// against a `Depth32Float` texture).
+    Hardware2x2,
+    /// `taps` Poisson-disk samples from [`POISSON_DISK_16`], rotated
+    /// per-fragment to avoid banding.
+    Pcf { taps: u32 },
+    /// Percentage-closer soft shadows: a blocker search over the Poisson
+    /// disk estimates the average blocker depth, `penumbra = (receiver -
+    /// avg_blocker) / avg_blocker * light_size` scales the PCF kernel
+    /// radius, and a second pass filters with that radius.
+    ///
+    /// Only `pcf_taps` reaches the shader (as [`LightSpaceUniform::taps`]);
+    /// the blocker search also takes `pcf_taps` samples, and
+    /// `blocker_search_taps` is not read.
+    Pcss {
+        blocker_search_taps: u32,
+        pcf_taps: u32,
+    },
+}
+
+/// Per-light shadow settings. Stored alongside [`ShadowMap`] so shadows can
+/// be tuned, or disabled entirely, without touching the render graph.
+#[derive(Copy, Clone, Debug)]
+pub struct ShadowSettings {
+    pub enabled: bool,
+    /// Depth bias subtracted before the shadow-map comparison, to fight
+    /// shadow acne on near-grazing surfaces.
+    pub bias: f32,
+    pub filter_mode: ShadowFilterMode,
+    /// World-space size of the light, used by [`ShadowFilterMode::Pcss`]'s
+    /// penumbra estimate. [`ShadowFilterMode::Pcf`] also uses it for its
+    /// kernel radius (`light_size * 0.05`); unused by `Hardware2x2`.
+    pub light_size: f32,
+}
+
+impl Default for ShadowSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bias: 0.0025,
+            filter_mode: ShadowFilterMode::Pcf { taps: 16 },
+            light_size: 0.3,
+        }
+    }
+}
+
+/// Matches the `LightSpaceUniform` read by the terrain/grass/entity fragment
+/// shaders to project a world position into the shadow map.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Zeroable, Pod)]
+pub struct LightSpaceUniform {
+    pub view_proj: [[f32; 4]; 4],
+    pub bias: f32,
+    pub light_size: f32,
+    pub filter_mode: u32,
+    pub taps: u32,
+}
+
+impl ShadowFilterMode {
+    fn shader_index(self) -> u32 {
+        match self {
+            ShadowFilterMode::Hardware2x2 => 0,
+            ShadowFilterMode::Pcf { .. } => 1,
+            ShadowFilterMode::Pcss { .. } => 2,
+        }
+    }
+
+    fn taps(self) -> u32 {
+        match self {
+            ShadowFilterMode::Hardware2x2 => 1,
+            ShadowFilterMode::Pcf { taps } => taps,
+            ShadowFilterMode::Pcss { pcf_taps, .. } => pcf_taps,
+        }
+    }
+}
+
+impl From<(ShadowSettings, [[f32; 4]; 4])> for LightSpaceUniform {
+    fn from((settings, view_proj): (ShadowSettings, [[f32; 4]; 4])) -> Self {
+        Self {
+            view_proj,
+            // With shadows disabled, a bias larger than the whole [0, 1] depth
+            // range makes every comparison pass, so `sample_shadow` reports fully lit.
+            bias: if settings.enabled { settings.bias } else { 2.0 },
+            light_size: settings.light_size,
+            filter_mode: settings.filter_mode.shader_index(),
+            taps: settings.filter_mode.taps(),
+        }
+    }
+}
+
+/// One cascade of the directional-light shadow map: a depth-only render
+/// target the light's geometry is rendered into, plus the view-projection
+/// matrix it was rendered with.
+pub struct ShadowCascade {
+    pub depth_texture: Arc<wgpu::Texture>,
+    pub depth_view: Arc<wgpu::TextureView>,
+    pub view_proj: [[f32; 4]; 4],
+}
+
+impl ShadowCascade {
+    /// Allocates this cascade's `Depth32Float` render target, sized
+    /// `SHADOW_MAP_SIZE` square, with `view_proj` as the identity until the
+    /// caller positions it via [`ShadowMap::update_cascade`].
+    #[must_use]
+    fn new(device: &wgpu::Device, label: &str) -> Self {
+        let depth_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: SHADOW_MAP_SIZE,
+                height: SHADOW_MAP_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Depth32Float,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let depth_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self {
+            depth_texture: Arc::new(depth_texture),
+            depth_view: Arc::new(depth_view),
+            view_proj: IDENTITY_MATRIX,
+        }
+    }
+}
+
+const IDENTITY_MATRIX: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// A directional-light shadow map: one or more cascades, the settings they
+/// were rendered with, and the uniform/bind group the main render passes
+/// sample them through.
+pub struct ShadowMap {
+    pub cascades: Vec<ShadowCascade>,
+    pub settings: ShadowSettings,
+    pub uniform_buffer: Arc<wgpu::Buffer>,
+    pub bind_group: Arc<wgpu::BindGroup>,
+    /// Bind group layout backing `bind_group`: binding 0 is the
+    /// [`LightSpaceUniform`] (read by both the depth pass's vertex shader and
+    /// the main passes' fragment shaders), binding 1 the comparison-sampled
+    /// depth texture of `cascades[0]`, binding 2 its comparison sampler.
+    /// Matches `render/shadow/shadow_sample.wgsl`'s `@group(2)` bindings, and
+    /// is what the terrain/grass/entity pipelines' layouts need to include to
+    /// sample shadows.
+    pub bind_group_layout: Arc<wgpu::BindGroupLayout>,
+    terrain_pipeline: wgpu::RenderPipeline,
+    entity_pipeline: wgpu::RenderPipeline,
+}
+
+impl ShadowMap {
+    /// Builds a single-cascade shadow map: allocates the cascade's depth
+    /// texture, the `LightSpaceUniform` buffer/bind group the depth pass and
+    /// the main passes both read, and the depth-only terrain/entity
+    /// pipelines that render into the cascade.
+    #[must_use]
+    pub fn new(device: &wgpu::Device, settings: ShadowSettings) -> Self {
+        let cascades = vec![ShadowCascade::new(device, "shadow_cascade_0")];
+
+        let uniform = LightSpaceUniform::from((settings, IDENTITY_MATRIX));
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("shadow_light_space_uniform"),
+            contents: bytemuck::bytes_of(&uniform),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let comparison_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("shadow_comparison_sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            compare: Some(wgpu::CompareFunction::LessEqual),
+            ..Default::default()
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("shadow_bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                    count: None,
+                },
+            ],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("shadow_bind_group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&cascades[0].depth_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&comparison_sampler),
+                },
+            ],
+        });
+
+        // The depth pass only needs `LightSpaceUniform` (for the vertex
+        // shader), but reuses `bind_group_layout`/`bind_group` rather than a
+        // second layout: a pipeline is free to ignore bindings its shader
+        // doesn't reference, and sharing keeps the depth pass and the main
+        // passes' shadow sampling pointed at the exact same uniform.
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("shadow_depth_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let depth_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("shadow_depth_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shadow_depth.wgsl").into()),
+        });
+        let terrain_pipeline = create_terrain_shadow_pipeline(device, &pipeline_layout, &depth_shader);
+        let entity_pipeline = create_entity_shadow_pipeline(device, &pipeline_layout, &depth_shader);
+
+        Self {
+            cascades,
+            settings,
+            uniform_buffer: Arc::new(uniform_buffer),
+            bind_group: Arc::new(bind_group),
+            bind_group_layout: Arc::new(bind_group_layout),
+            terrain_pipeline,
+            entity_pipeline,
+        }
+    }
+
+    /// Re-uploads the light-space uniform for `cascade`, e.g. after the sun
+    /// moves or [`ShadowSettings`] changes. Doesn't touch the depth texture
+    /// itself; that's repopulated by a normal render pass into `depth_view`.
+    pub fn update_cascade(&mut self, queue: &wgpu::Queue, cascade: usize, view_proj: [[f32; 4]; 4]) {
+        self.cascades[cascade].view_proj = view_proj;
+
+        let uniform = LightSpaceUniform::from((self.settings, view_proj));
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniform));
+    }
+
+    /// Begins the depth-only render pass that bakes `cascade`'s shadow map:
+    /// clears its depth texture and binds the pass's `LightSpaceUniform`.
+    /// The caller sets [`ShadowMap::terrain_pipeline`]/[`ShadowMap::entity_pipeline`]
+    /// and issues draws for whatever geometry should cast a shadow this frame.
+    pub fn begin_cascade_pass<'a>(
+        &'a self,
+        encoder: &'a mut wgpu::CommandEncoder,
+        cascade: usize,
+    ) -> wgpu::RenderPass<'a> {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("shadow_cascade_pass"),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.cascades[cascade].depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass
+    }
+
+    #[must_use]
+    pub fn terrain_pipeline(&self) -> &wgpu::RenderPipeline {
+        &self.terrain_pipeline
+    }
+
+    #[must_use]
+    pub fn entity_pipeline(&self) -> &wgpu::RenderPipeline {
+        &self.entity_pipeline
+    }
+}
+
+/// Splices `shadow_sample.wgsl`'s `sample_shadow` (and its `@group(2)`
+/// bindings) ahead of `main_shader_source`, so whatever builds the terrain,
+/// grass and entity fragment shaders can call `sample_shadow(world_pos)` from
+/// `fs_main` instead of pasting the shadow-sampling source in by hand.
+///
+/// This crate has no terrain/grass/entity pipeline-construction module of its
+/// own (`render::pipeline` isn't part of this tree) to call this from, so it
+/// has no caller here; it's the one piece of real glue those modules need
+/// once they exist upstream, in place of a note saying to paste the shader in.
+/// Those pipelines' bind group layouts also need `ShadowMap::bind_group_layout`
+/// appended as `@group(2)`.
+#[must_use]
+pub fn splice_shadow_sampling(main_shader_source: &str) -> String {
+    format!("{}\n{}", include_str!("shadow_sample.wgsl"), main_shader_source)
+}
+
+fn shadow_depth_stencil_state() -> wgpu::DepthStencilState {
+    wgpu::DepthStencilState {
+        format: wgpu::TextureFormat::Depth32Float,
+        depth_write_enabled: true,
+        depth_compare: wgpu::CompareFunction::Less,
+        stencil: wgpu::StencilState::default(),
+        bias: wgpu::DepthBiasState::default(),
+    }
+}
+
+/// Builds the depth-only pipeline that renders `TerrainVertex` geometry from
+/// the light's point of view into a [`ShadowCascade`]'s depth texture.
+#[must_use]
+pub fn create_terrain_shadow_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("terrain_shadow_pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: "vs_main",
+            buffers: &[TerrainVertex::desc()],
+        },
+        fragment: None,
+        primitive: wgpu::PrimitiveState {
+            // Cull the opposite winding to the main pass to reduce peter-panning.
+            cull_mode: Some(wgpu::Face::Front),
+            ..Default::default()
+        },
+        depth_stencil: Some(shadow_depth_stencil_state()),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+    })
+}
+
+/// Builds the depth-only pipeline that renders `EntityVertex` geometry from
+/// the light's point of view into a [`ShadowCascade`]'s depth texture.
+#[must_use]
+pub fn create_entity_shadow_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("entity_shadow_pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: "vs_main",
+            buffers: &[EntityVertex::desc()],
+        },
+        fragment: None,
+        primitive: wgpu::PrimitiveState {
+            cull_mode: Some(wgpu::Face::Front),
+            ..Default::default()
+        },
+        depth_stencil: Some(shadow_depth_stencil_state()),
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+    })
+}
@@ -0,0 +1,22 @@
+/// A fixed Poisson-disk sample set in `[-1, 1]`, used to offset shadow-map
+/// taps so PCF/PCSS don't just sample a regular (and visibly banded) grid.
+/// Rotated per-fragment in the shader by an angle hashed from the fragment's
+/// shadow-map UV so the pattern doesn't repeat across the screen either.
+pub const POISSON_DISK_16: [[f32; 2]; 16] = [
+    [-0.94201624, -0.39906216],
+    [0.94558609, -0.76890725],
+    [-0.094184101, -0.92938870],
+    [0.34495938, 0.29387760],
+    [-0.91588581, 0.45771432],
+    [-0.81544232, -0.87912464],
+    [-0.38277543, 0.27676845],
+    [0.97484398, 0.75648379],
+    [0.44323325, -0.97511554],
+    [0.53742981, -0.47373420],
+    [-0.26496911, -0.41893023],
+    [0.79197514, 0.19090188],
+    [-0.24188840, 0.99706507],
+    [-0.81409955, 0.91437590],
+    [0.19984126, 0.78641367],
+    [0.14383161, -0.14100790],
+];